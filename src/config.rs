@@ -0,0 +1,124 @@
+use pyo3::prelude::*;
+use serialport::SerialPort;
+use std::time::{Duration, Instant};
+use std::rc::Rc;
+
+// Helper function to convert serialport errors to PyErr
+fn to_py_err(e: impl std::error::Error) -> PyErr {
+    pyo3::exceptions::PyIOError::new_err(format!("Serial port error: {}", e))
+}
+
+/// Writer for the sensor's control (CLI) UART.
+///
+/// The data UART is handled by `RadarReader`; this opens the separate command
+/// port so a `.cfg` profile and `sensorStart`/`sensorStop` commands can be
+/// pushed from the same process, turning the crate into a round-trip driver.
+#[pyclass(unsendable)]
+pub struct RadarConfig {
+    port: Box<dyn SerialPort>,
+    timeout: Duration,
+    debug: bool,
+    _unsendable: Rc<()>,
+}
+
+#[pymethods]
+impl RadarConfig {
+    #[new]
+    #[pyo3(signature = (port_name, baudrate=115200, timeout_ms=1000, debug=None))]
+    pub fn new(port_name: &str, baudrate: u32, timeout_ms: u64, debug: Option<bool>) -> PyResult<Self> {
+        let port = serialport::new(port_name, baudrate)  // CLI UART defaults to 115200 baud
+            .timeout(Duration::from_millis(50))  // Short reads so we can poll for the prompt
+            .flow_control(serialport::FlowControl::None)
+            .open()
+            .map_err(to_py_err)?;
+
+        Ok(RadarConfig {
+            port,
+            timeout: Duration::from_millis(timeout_ms),
+            debug: debug.unwrap_or(false),
+            _unsendable: Rc::new(()),
+        })
+    }
+
+    /// Send a list of CLI commands (e.g. the lines of a `.cfg` profile),
+    /// returning the sensor's response for each. Blank lines and `%` comments
+    /// are skipped, matching the `.cfg` file format.
+    pub fn send_config(&mut self, lines: Vec<String>) -> PyResult<Vec<String>> {
+        let mut responses = Vec::new();
+        for line in lines {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('%') {
+                continue;
+            }
+            responses.push(self.send_command(trimmed)?);
+        }
+        Ok(responses)
+    }
+
+    /// Send a single CLI command terminated by CRLF and return the sensor's
+    /// acknowledgement text, raising if it reports an error or never replies.
+    pub fn send_command(&mut self, command: &str) -> PyResult<String> {
+        if self.debug {
+            println!("-> {}", command);
+        }
+
+        self.port
+            .write_all(format!("{}\r\n", command).as_bytes())
+            .map_err(to_py_err)?;
+        self.port.flush().map_err(to_py_err)?;
+
+        self.read_ack(command)
+    }
+
+    /// Send `sensorStart` to begin the configured frame acquisition.
+    pub fn start_sensor(&mut self) -> PyResult<String> {
+        self.send_command("sensorStart")
+    }
+
+    /// Send `sensorStop` to halt acquisition.
+    pub fn stop_sensor(&mut self) -> PyResult<String> {
+        self.send_command("sensorStop")
+    }
+}
+
+impl RadarConfig {
+    /// Accumulate the response until the sensor prints its `Done`/`Error`
+    /// prompt or the timeout elapses.
+    fn read_ack(&mut self, command: &str) -> PyResult<String> {
+        let start = Instant::now();
+        let mut response = String::new();
+        let mut temp_buf = [0u8; 256];
+
+        while start.elapsed() < self.timeout {
+            Python::with_gil(|py| py.check_signals())?;
+
+            match self.port.read(&mut temp_buf) {
+                Ok(n) if n > 0 => {
+                    response.push_str(&String::from_utf8_lossy(&temp_buf[..n]));
+                    if response.contains("Done") {
+                        if self.debug {
+                            println!("<- {}", response.trim());
+                        }
+                        return Ok(response.trim().to_string());
+                    }
+                    if response.contains("Error") {
+                        return Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                            "Command '{}' rejected by sensor: {}",
+                            command,
+                            response.trim()
+                        )));
+                    }
+                }
+                Ok(_) => {}
+                Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                Err(e) => return Err(to_py_err(e)),
+            }
+        }
+
+        Err(pyo3::exceptions::PyIOError::new_err(format!(
+            "Timed out waiting for acknowledgement to '{}' (got: {:?})",
+            command,
+            response.trim()
+        )))
+    }
+}
@@ -0,0 +1,187 @@
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+use crate::types::{Header, RadarPacket, MAGIC_WORD};
+
+/// Stateful framing codec shared by every byte source.
+///
+/// Bytes are appended with [`FrameDecoder::feed`] and complete packets are
+/// pulled out with [`FrameDecoder::next`]; partial data stays buffered across
+/// calls so the exact same framing runs over serial, UDP, and offline replay.
+#[derive(Debug, Default)]
+pub struct FrameDecoder {
+    buffer: Vec<u8>,
+}
+
+impl FrameDecoder {
+    pub fn new() -> Self {
+        FrameDecoder::default()
+    }
+
+    /// Create a decoder pre-loaded with bytes from an arbitrary source, such
+    /// as a recorded capture file read into memory.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        FrameDecoder {
+            buffer: bytes.to_vec(),
+        }
+    }
+
+    /// Append raw bytes to the internal buffer.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Number of bytes currently buffered and not yet framed.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Return the next complete packet parseable from the buffer, consuming
+    /// its bytes. Returns `None` when only partial data is available, leaving
+    /// it buffered for a later call.
+    pub fn next(&mut self) -> Option<RadarPacket> {
+        loop {
+            // Locate the magic word; drop everything ahead of it.
+            match find_magic_word(&self.buffer) {
+                Some(pos) => {
+                    if pos > 0 {
+                        self.buffer.drain(..pos);
+                    }
+                }
+                None => {
+                    // No magic word yet: keep only the trailing bytes that
+                    // could still be the start of one.
+                    let keep = MAGIC_WORD.len() - 1;
+                    if self.buffer.len() > keep {
+                        let drop = self.buffer.len() - keep;
+                        self.buffer.drain(..drop);
+                    }
+                    return None;
+                }
+            }
+
+            // Need the magic word plus the 32-byte header before we can frame.
+            if self.buffer.len() < 40 {
+                return None;
+            }
+
+            let header = parse_header(&self.buffer[8..40]);
+            if !validate_header(&header) {
+                // False positive magic word: skip it and resync.
+                self.buffer.drain(..MAGIC_WORD.len());
+                continue;
+            }
+
+            let total = header.total_packet_len as usize;
+            if self.buffer.len() < total {
+                // Body not fully received yet.
+                return None;
+            }
+
+            let data = self.buffer[40..total].to_vec();
+            self.buffer.drain(..total);
+            return Some(RadarPacket { header, data });
+        }
+    }
+}
+
+/// Find the offset of the magic word within `buffer`, if present.
+fn find_magic_word(buffer: &[u8]) -> Option<usize> {
+    buffer
+        .windows(MAGIC_WORD.len())
+        .position(|window| window == MAGIC_WORD)
+}
+
+/// Parse the 32-byte little-endian header that follows the magic word.
+fn parse_header(bytes: &[u8]) -> Header {
+    let mut rdr = Cursor::new(bytes);
+    Header {
+        magic: MAGIC_WORD.to_vec(),
+        version: rdr.read_u32::<LittleEndian>().unwrap(),
+        total_packet_len: rdr.read_u32::<LittleEndian>().unwrap(),
+        platform: rdr.read_u32::<LittleEndian>().unwrap(),
+        frame_number: rdr.read_u32::<LittleEndian>().unwrap(),
+        time_cpu_cycles: rdr.read_u32::<LittleEndian>().unwrap(),
+        num_detected_obj: rdr.read_u32::<LittleEndian>().unwrap(),
+        num_tlv: rdr.read_u32::<LittleEndian>().unwrap(),
+    }
+}
+
+/// Basic sanity checks on a parsed header.
+fn validate_header(header: &Header) -> bool {
+    header.total_packet_len >= 40
+        && header.total_packet_len <= 4096
+        && header.total_packet_len % 32 == 0
+        && header.num_detected_obj <= 100
+        && header.num_tlv <= 10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build the on-wire bytes for one packet with the given payload.
+    fn packet_bytes(payload: &[u8]) -> Vec<u8> {
+        let total = (40 + payload.len()) as u32;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC_WORD);
+        bytes.extend_from_slice(&0u32.to_le_bytes()); // version
+        bytes.extend_from_slice(&total.to_le_bytes()); // total_packet_len
+        bytes.extend_from_slice(&0u32.to_le_bytes()); // platform
+        bytes.extend_from_slice(&7u32.to_le_bytes()); // frame_number
+        bytes.extend_from_slice(&0u32.to_le_bytes()); // time_cpu_cycles
+        bytes.extend_from_slice(&0u32.to_le_bytes()); // num_detected_obj
+        bytes.extend_from_slice(&0u32.to_le_bytes()); // num_tlv
+        bytes.extend_from_slice(payload);
+        bytes
+    }
+
+    #[test]
+    fn frames_a_single_packet() {
+        let mut decoder = FrameDecoder::new();
+        decoder.feed(&packet_bytes(&[0u8; 24]));
+
+        let packet = decoder.next().expect("a complete packet");
+        assert_eq!(packet.header.total_packet_len, 64);
+        assert_eq!(packet.header.frame_number, 7);
+        assert_eq!(packet.data.len(), 24);
+        assert!(decoder.next().is_none());
+        assert_eq!(decoder.buffered(), 0);
+    }
+
+    #[test]
+    fn resyncs_past_leading_garbage() {
+        let mut bytes = vec![0xAA, 0xBB, 0xCC];
+        bytes.extend_from_slice(&packet_bytes(&[0u8; 24]));
+
+        let mut decoder = FrameDecoder::new();
+        decoder.feed(&bytes);
+        assert!(decoder.next().is_some());
+    }
+
+    #[test]
+    fn buffers_partial_packet_across_feeds() {
+        let bytes = packet_bytes(&[0u8; 24]);
+        let mut decoder = FrameDecoder::new();
+
+        decoder.feed(&bytes[..30]);
+        assert!(decoder.next().is_none());
+
+        decoder.feed(&bytes[30..]);
+        assert!(decoder.next().is_some());
+    }
+
+    #[test]
+    fn skips_false_magic_word() {
+        // A magic word followed by an invalid header, then a real packet.
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC_WORD);
+        bytes.extend_from_slice(&[0xFFu8; 32]); // total_packet_len huge -> invalid
+        bytes.extend_from_slice(&packet_bytes(&[0u8; 24]));
+
+        let mut decoder = FrameDecoder::new();
+        decoder.feed(&bytes);
+        let packet = decoder.next().expect("should resync to the valid packet");
+        assert_eq!(packet.header.total_packet_len, 64);
+    }
+}
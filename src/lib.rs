@@ -1,17 +1,24 @@
 use pyo3::prelude::*;
 
 mod types;
+mod decoder;
 mod reader;
 mod udp;
+mod config;
 
 /// A Python module implemented in Rust.
 #[pymodule]
 fn mmwserial(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add("RadarReader", _py.get_type::<reader::RadarReader>())?;
+    m.add("RadarConfig", _py.get_type::<config::RadarConfig>())?;
     m.add_class::<udp::UDPReader>()?;
+    m.add_class::<types::DecodedFrame>()?;
+    m.add_class::<types::PollResult>()?;
     Ok(())
 }
 
+pub use config::RadarConfig;
+pub use decoder::FrameDecoder;
 pub use reader::RadarReader;
 pub use types::*;
-pub use udp::UDPReader; 
\ No newline at end of file
+pub use udp::UDPReader;
\ No newline at end of file
@@ -1,5 +1,9 @@
 use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use byteorder::{LittleEndian, ReadBytesExt};
 use std::fmt;
+use std::io::Cursor;
+use std::time::{Duration, Instant};
 
 /// Message types used in TI mmWave radar output
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -15,6 +19,24 @@ pub enum MessageType {
     TemperatureStats = 9,
 }
 
+impl MessageType {
+    /// Map a raw TLV type word to a known `MessageType`, if recognised.
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            1 => Some(MessageType::DetectedPoints),
+            2 => Some(MessageType::RangeProfile),
+            3 => Some(MessageType::NoiseProfile),
+            4 => Some(MessageType::AzimutStaticHeatMap),
+            5 => Some(MessageType::RangeDopplerHeatMap),
+            6 => Some(MessageType::Stats),
+            7 => Some(MessageType::DetectedPointsSideInfo),
+            8 => Some(MessageType::AzimutElevationStaticHeatMap),
+            9 => Some(MessageType::TemperatureStats),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for MessageType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self)
@@ -121,4 +143,277 @@ impl RadarPacket {
     fn new_py(header: Header, data: Vec<u8>) -> Self {
         Self::new(header, data)
     }
-} 
\ No newline at end of file
+
+    /// Walk the TLV payload and return the typed, numpy-friendly sections.
+    ///
+    /// The payload is a sequence of `header.num_tlv` TLVs, each prefixed by a
+    /// `TlvHeader` (`typ`, `length`) followed by `length` bytes. Unknown types
+    /// are skipped by advancing `length` bytes so future firmware can add
+    /// sections without breaking decoding.
+    pub fn decode(&self) -> PyResult<DecodedFrame> {
+        let mut frame = DecodedFrame::default();
+        let mut cursor = Cursor::new(&self.data);
+        let total = self.data.len() as u64;
+
+        for _ in 0..self.header.num_tlv {
+            // A TLV header is two little-endian u32s; stop cleanly if the
+            // payload ends early rather than panicking.
+            if total - cursor.position() < 8 {
+                return Err(PyValueError::new_err(
+                    "truncated TLV header in packet payload",
+                ));
+            }
+            let typ = cursor.read_u32::<LittleEndian>().unwrap();
+            let length = cursor.read_u32::<LittleEndian>().unwrap() as u64;
+
+            let start = cursor.position();
+            if start + length > total {
+                return Err(PyValueError::new_err(format!(
+                    "TLV length {} overflows packet payload ({} bytes remaining)",
+                    length,
+                    total - start
+                )));
+            }
+
+            match MessageType::from_u32(typ) {
+                Some(MessageType::DetectedPoints) => {
+                    // Bound by what the TLV actually carries (16 bytes/record),
+                    // not just the header count, so a bogus `num_detected_obj`
+                    // can't drive the cursor past the payload.
+                    let n = (self.header.num_detected_obj as usize).min((length / 16) as usize);
+                    frame.detected_points = read_f32_array(&mut cursor, n * 4);
+                }
+                Some(MessageType::DetectedPointsSideInfo) => {
+                    let n = (self.header.num_detected_obj as usize).min((length / 4) as usize);
+                    frame.side_info = read_i16_array(&mut cursor, n * 2);
+                }
+                Some(MessageType::RangeProfile) => {
+                    frame.range_profile = read_u16_array(&mut cursor, (length / 2) as usize);
+                }
+                Some(MessageType::NoiseProfile) => {
+                    frame.noise_profile = read_u16_array(&mut cursor, (length / 2) as usize);
+                }
+                Some(MessageType::AzimutStaticHeatMap) => {
+                    frame.azimuth_static_heatmap = read_i16_array(&mut cursor, (length / 2) as usize);
+                }
+                Some(MessageType::RangeDopplerHeatMap) => {
+                    frame.range_doppler_heatmap = read_i16_array(&mut cursor, (length / 2) as usize);
+                }
+                Some(MessageType::AzimutElevationStaticHeatMap) => {
+                    frame.azimuth_elevation_heatmap = read_i16_array(&mut cursor, (length / 2) as usize);
+                }
+                // Stats / TemperatureStats / unknown types: skip the payload.
+                _ => {}
+            }
+
+            // Always resync to the declared end of the TLV so a partially
+            // consumed or unknown section cannot desynchronise the walk.
+            cursor.set_position(start + length);
+        }
+
+        Ok(frame)
+    }
+}
+
+/// Read up to `count` little-endian `f32` values, stopping at the payload end.
+fn read_f32_array(cursor: &mut Cursor<&Vec<u8>>, count: usize) -> Vec<f32> {
+    let mut values = Vec::with_capacity(count);
+    for _ in 0..count {
+        match cursor.read_f32::<LittleEndian>() {
+            Ok(v) => values.push(v),
+            Err(_) => break,
+        }
+    }
+    values
+}
+
+/// Read up to `count` little-endian `u16` values, stopping at the payload end.
+fn read_u16_array(cursor: &mut Cursor<&Vec<u8>>, count: usize) -> Vec<u16> {
+    let mut values = Vec::with_capacity(count);
+    for _ in 0..count {
+        match cursor.read_u16::<LittleEndian>() {
+            Ok(v) => values.push(v),
+            Err(_) => break,
+        }
+    }
+    values
+}
+
+/// Read up to `count` little-endian `i16` values, stopping at the payload end.
+fn read_i16_array(cursor: &mut Cursor<&Vec<u8>>, count: usize) -> Vec<i16> {
+    let mut values = Vec::with_capacity(count);
+    for _ in 0..count {
+        match cursor.read_i16::<LittleEndian>() {
+            Ok(v) => values.push(v),
+            Err(_) => break,
+        }
+    }
+    values
+}
+
+/// Outcome of a single nonblocking `poll` on a reader.
+///
+/// Distinguishes three states so a Python event loop can multiplex several
+/// readers: a decoded `Packet`, `Pending` (nothing ready yet, but a frame is
+/// expected — sleep `sleep_for` seconds before polling again), and `Idle`
+/// (no frame has ever been seen, so there is no deadline to wait on).
+#[pyclass(frozen)]
+#[derive(Debug, Clone)]
+pub struct PollResult {
+    /// A fully decoded packet, present only in the `Packet` state.
+    #[pyo3(get)]
+    pub packet: Option<RadarPacket>,
+    /// Seconds until the next expected frame, present only in the `Pending`
+    /// state — the soft deadline the caller should sleep until.
+    #[pyo3(get)]
+    pub sleep_for: Option<f64>,
+}
+
+impl PollResult {
+    pub fn packet(packet: RadarPacket) -> Self {
+        PollResult { packet: Some(packet), sleep_for: None }
+    }
+
+    pub fn pending(sleep_for: Duration) -> Self {
+        PollResult { packet: None, sleep_for: Some(sleep_for.as_secs_f64()) }
+    }
+
+    pub fn idle() -> Self {
+        PollResult { packet: None, sleep_for: None }
+    }
+
+    /// Non-packet outcome: `Pending` with the time remaining until
+    /// `last_frame_time + frame_period`, or `Idle` when no frame has been seen.
+    pub fn waiting(last_frame_time: Option<Instant>, frame_period: Duration, now: Instant) -> Self {
+        match last_frame_time {
+            Some(last) => Self::pending((last + frame_period).saturating_duration_since(now)),
+            None => Self::idle(),
+        }
+    }
+}
+
+#[pymethods]
+impl PollResult {
+    /// Discriminant name: `"packet"`, `"pending"`, or `"idle"`.
+    #[getter]
+    fn kind(&self) -> &'static str {
+        if self.packet.is_some() {
+            "packet"
+        } else if self.sleep_for.is_some() {
+            "pending"
+        } else {
+            "idle"
+        }
+    }
+}
+
+/// Typed view of a decoded radar frame.
+///
+/// Each section is a flat, numpy-friendly buffer. Point-cloud records are
+/// laid out as consecutive `x, y, z, doppler` (`detected_points`) and
+/// `snr, noise` (`side_info`) tuples; heatmaps are row-major 2-D arrays.
+#[pyclass(frozen)]
+#[derive(Debug, Clone, Default)]
+pub struct DecodedFrame {
+    #[pyo3(get)]
+    pub detected_points: Vec<f32>,
+    #[pyo3(get)]
+    pub side_info: Vec<i16>,
+    #[pyo3(get)]
+    pub range_profile: Vec<u16>,
+    #[pyo3(get)]
+    pub noise_profile: Vec<u16>,
+    #[pyo3(get)]
+    pub azimuth_static_heatmap: Vec<i16>,
+    #[pyo3(get)]
+    pub range_doppler_heatmap: Vec<i16>,
+    #[pyo3(get)]
+    pub azimuth_elevation_heatmap: Vec<i16>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(num_detected_obj: u32, num_tlv: u32) -> Header {
+        Header {
+            magic: MAGIC_WORD.to_vec(),
+            version: 0,
+            total_packet_len: 0,
+            platform: 0,
+            frame_number: 0,
+            time_cpu_cycles: 0,
+            num_detected_obj,
+            num_tlv,
+        }
+    }
+
+    #[test]
+    fn decodes_detected_points() {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&1u32.to_le_bytes()); // typ: DetectedPoints
+        payload.extend_from_slice(&16u32.to_le_bytes()); // length: one record
+        for v in [1.0f32, 2.0, 3.0, 4.0] {
+            payload.extend_from_slice(&v.to_le_bytes());
+        }
+
+        let frame = RadarPacket::new(header(1, 1), payload).decode().unwrap();
+        assert_eq!(frame.detected_points, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn skips_unknown_tlv_type() {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&999u32.to_le_bytes()); // unknown type
+        payload.extend_from_slice(&4u32.to_le_bytes());
+        payload.extend_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+
+        let frame = RadarPacket::new(header(0, 1), payload).decode().unwrap();
+        assert!(frame.detected_points.is_empty());
+    }
+
+    #[test]
+    fn errors_on_truncated_tlv_header() {
+        // num_tlv claims a TLV but fewer than 8 bytes remain.
+        let result = RadarPacket::new(header(0, 1), vec![0u8; 4]).decode();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn errors_on_tlv_length_overflow() {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&2u32.to_le_bytes()); // RangeProfile
+        payload.extend_from_slice(&100u32.to_le_bytes()); // length overflows payload
+        payload.extend_from_slice(&[0u8, 0]);
+
+        let result = RadarPacket::new(header(0, 1), payload).decode();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn bounds_detected_points_by_tlv_length() {
+        // Header claims 100 objects but the TLV carries none: must not panic.
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&1u32.to_le_bytes()); // DetectedPoints
+        payload.extend_from_slice(&0u32.to_le_bytes()); // length: empty
+
+        let frame = RadarPacket::new(header(100, 1), payload).decode().unwrap();
+        assert!(frame.detected_points.is_empty());
+    }
+
+    #[test]
+    fn poll_result_waiting_states() {
+        let now = Instant::now();
+        assert_eq!(
+            PollResult::waiting(None, Duration::from_millis(100), now).kind(),
+            "idle"
+        );
+
+        let pending = PollResult::waiting(Some(now), Duration::from_millis(100), now);
+        assert_eq!(pending.kind(), "pending");
+        assert!(pending.sleep_for.unwrap() <= 0.1 + 1e-6);
+
+        let packet = PollResult::packet(RadarPacket::new(header(0, 0), vec![]));
+        assert_eq!(packet.kind(), "packet");
+    }
+}
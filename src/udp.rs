@@ -1,10 +1,13 @@
-use std::net::{SocketAddr, UdpSocket};
-use std::time::Duration;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use pyo3::prelude::*;
 use pyo3::exceptions::PyIOError;
 use socket2::{Socket, Domain, Protocol, Type};
 
+use crate::decoder::FrameDecoder;
+use crate::types::{PollResult, RadarPacket};
+
 #[derive(Error, Debug)]
 pub enum UDPError {
     #[error("Socket error: {0}")]
@@ -27,65 +30,185 @@ impl From<UDPError> for PyErr {
     }
 }
 
+/// Join an IPv4 or IPv6 multicast group on the chosen interface.
+///
+/// The group's address family selects the join call; `interface` is an IPv4
+/// address for v4 groups (defaulting to `0.0.0.0`, the system default) and a
+/// numeric interface index for v6 groups (defaulting to `0`).
+fn join_multicast(socket: &Socket, group: &str, interface: Option<&str>) -> Result<(), UDPError> {
+    let invalid = |msg: String| {
+        UDPError::SocketError(std::io::Error::new(std::io::ErrorKind::InvalidInput, msg))
+    };
+
+    if let Ok(group) = group.parse::<Ipv4Addr>() {
+        let iface = match interface {
+            Some(s) => s.parse::<Ipv4Addr>()
+                .map_err(|e| invalid(format!("invalid IPv4 multicast interface '{}': {}", s, e)))?,
+            None => Ipv4Addr::UNSPECIFIED,
+        };
+        socket.join_multicast_v4(&group, &iface).map_err(UDPError::SocketError)
+    } else if let Ok(group) = group.parse::<Ipv6Addr>() {
+        let index = match interface {
+            Some(s) => s.parse::<u32>()
+                .map_err(|e| invalid(format!("invalid IPv6 interface index '{}': {}", s, e)))?,
+            None => 0,
+        };
+        socket.join_multicast_v6(&group, index).map_err(UDPError::SocketError)
+    } else {
+        Err(invalid(format!("invalid multicast group address '{}'", group)))
+    }
+}
+
 #[pyclass]
 pub struct UDPReader {
     socket: UdpSocket,
     frame_size: usize,
     timeout: Duration,
+    decoder: FrameDecoder,
+    frame_period: Duration,
+    last_frame_time: Option<Instant>,
 }
 
 #[pymethods]
 impl UDPReader {
     #[new]
-    pub fn new(interface: &str, port: u16, frame_size: usize, timeout_ms: u64) -> PyResult<Self> {
-        let addr = format!("{}:{}", interface, port).parse::<SocketAddr>()
+    #[pyo3(signature = (interface, port, frame_size, timeout_ms, frame_period_ms=100, multicast_group=None, multicast_interface=None))]
+    pub fn new(
+        interface: &str,
+        port: u16,
+        frame_size: usize,
+        timeout_ms: u64,
+        frame_period_ms: u64,
+        multicast_group: Option<String>,
+        multicast_interface: Option<String>,
+    ) -> PyResult<Self> {
+        // Bracket bare IPv6 literals (e.g. `::`) so they parse as an
+        // address:port pair; already bracketed input (`[::]`) is kept as is.
+        let host = if interface.contains(':') && !interface.starts_with('[') {
+            format!("[{}]", interface)
+        } else {
+            interface.to_string()
+        };
+        let addr = format!("{}:{}", host, port).parse::<SocketAddr>()
             .map_err(|e| UDPError::SocketError(std::io::Error::new(std::io::ErrorKind::InvalidInput, e)))?;
-        
+
+        // Match the socket's address family to the parsed bind address so IPv6
+        // (including `[::]`) works, not just IPv4 unicast.
+        let domain = match addr {
+            SocketAddr::V4(_) => Domain::IPV4,
+            SocketAddr::V6(_) => Domain::IPV6,
+        };
+
         // Create socket with socket2 for more control
-        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))
+        let socket = Socket::new(domain, Type::DGRAM, Some(Protocol::UDP))
             .map_err(UDPError::SocketError)?;
-            
+
         // Set socket options
         socket.set_reuse_address(true)
             .map_err(UDPError::SocketError)?;
-            
+
         // Set receive buffer size (default is usually 65536)
         socket.set_recv_buffer_size(65536)
             .map_err(UDPError::SocketError)?;
-            
+
         // Bind the socket
         socket.bind(&addr.into())
             .map_err(UDPError::SocketError)?;
-            
+
+        // Optionally join a multicast group so DCA1000-style broadcast captures
+        // can be received regardless of the source interface.
+        if let Some(group) = multicast_group {
+            join_multicast(&socket, &group, multicast_interface.as_deref())?;
+        }
+
         // Convert to std::net::UdpSocket
         let socket: UdpSocket = socket.into();
-        
-        // Set read timeout
-        socket.set_read_timeout(Some(Duration::from_millis(timeout_ms)))
+
+        // Nonblocking mode so `poll` can drain without stalling a shared event
+        // loop; `read_frame` layers its own timeout on top.
+        socket.set_nonblocking(true)
             .map_err(UDPError::SocketError)?;
-        
+
         Ok(Self {
             socket,
             frame_size,
             timeout: Duration::from_millis(timeout_ms),
+            decoder: FrameDecoder::new(),
+            frame_period: Duration::from_millis(frame_period_ms),
+            last_frame_time: None,
         })
     }
 
+    /// Nonblocking acquisition step, mirroring [`RadarReader::poll`].
+    ///
+    /// Drains whatever datagrams are currently queued into the decode buffer
+    /// and tries to emit a completed packet; otherwise returns the soft
+    /// deadline of the next expected frame so the caller can sleep precisely.
+    pub fn poll(&mut self) -> PyResult<PollResult> {
+        let mut buffer = vec![0u8; self.frame_size];
+        loop {
+            match self.socket.recv(&mut buffer) {
+                Ok(received) => self.decoder.feed(&buffer[..received]),
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => break,
+                Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => break,
+                Err(e) => return Err(UDPError::SocketError(e).into()),
+            }
+        }
+
+        if let Some(packet) = self.decoder.next() {
+            self.last_frame_time = Some(Instant::now());
+            return Ok(PollResult::packet(packet));
+        }
+
+        Ok(PollResult::waiting(self.last_frame_time, self.frame_period, Instant::now()))
+    }
+
     pub fn read_frame(&self) -> PyResult<Vec<u8>> {
         let mut buffer = vec![0u8; self.frame_size];
-        let received = self.socket.recv(&mut buffer)
-            .map_err(UDPError::SocketError)?;
-        
+        let start = Instant::now();
+
+        // The socket is nonblocking; spin until a datagram arrives or we hit
+        // the configured timeout.
+        let received = loop {
+            match self.socket.recv(&mut buffer) {
+                Ok(received) => break received,
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock
+                    || e.kind() == std::io::ErrorKind::TimedOut =>
+                {
+                    if start.elapsed() >= self.timeout {
+                        return Err(UDPError::Timeout.into());
+                    }
+                    std::thread::sleep(Duration::from_micros(100));
+                }
+                Err(e) => return Err(UDPError::SocketError(e).into()),
+            }
+        };
+
         if received != self.frame_size {
             return Err(UDPError::IncompleteFrame {
                 expected: self.frame_size,
                 received,
             }.into());
         }
-        
+
         Ok(buffer)
     }
 
+    /// Receive one datagram, feed it through the shared [`FrameDecoder`], and
+    /// return the next complete packet if one is now parseable.
+    pub fn read_packet(&mut self) -> PyResult<Option<RadarPacket>> {
+        let mut buffer = vec![0u8; self.frame_size];
+        // The socket is nonblocking; a missing datagram is the normal
+        // "no data yet" path, not an error.
+        match self.socket.recv(&mut buffer) {
+            Ok(received) => self.decoder.feed(&buffer[..received]),
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock
+                || e.kind() == std::io::ErrorKind::TimedOut => {}
+            Err(e) => return Err(UDPError::SocketError(e).into()),
+        }
+        Ok(self.decoder.next())
+    }
+
     pub fn read_frames(&self, num_frames: usize) -> PyResult<Vec<Vec<u8>>> {
         let mut frames = Vec::with_capacity(num_frames);
         
@@ -111,7 +234,7 @@ mod tests {
 
     #[test]
     fn test_udp_reader() {
-        let reader = UDPReader::new("127.0.0.1", 12345, 1024, 1000).unwrap();
+        let reader = UDPReader::new("127.0.0.1", 12345, 1024, 1000, 100, None, None).unwrap();
         // Add more tests as needed
     }
 } 
\ No newline at end of file